@@ -1,10 +1,14 @@
 pub use secrecy;
 pub use serde;
+pub use subtle;
 
 /// Generate a numeric id type
 ///
-/// The `id!` macro generates a numeric id type that wraps a `u64`. The type implements common
-/// traits and conversations that make it easy to use.
+/// The `id!` macro generates a numeric id type. The type implements common traits and
+/// conversations that make it easy to use.
+///
+/// The backing primitive defaults to `u64`, but can be set to any other integer type (for example
+/// `u32` for legacy GitHub ids or `u128` for Snowflake-style ids) by naming it explicitly.
 ///
 /// # Example
 ///
@@ -16,24 +20,40 @@ pub use serde;
 /// let id: UserId = 42.into();
 /// println!("User {} registered", id);
 /// ```
+///
+/// ```rust
+/// use idtype::id;
+///
+/// id!(SmallId: u32);
+///
+/// let id: SmallId = 42u32.into();
+/// println!("User {} registered", id);
+/// ```
 #[macro_export]
 macro_rules! id {
     (
         $(#[$meta:meta])*
         $id:ident
+    ) => {
+        $crate::id!($(#[$meta])* $id: u64);
+    };
+
+    (
+        $(#[$meta:meta])*
+        $id:ident: $ty:ty
     ) => {
         $(#[$meta])*
         #[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, $crate::serde::Deserialize, $crate::serde::Serialize)]
-        pub struct $id(u64);
+        pub struct $id($ty);
 
         impl $id {
             /// Initializes a new id.
-            pub fn new(id: u64) -> Self {
+            pub fn new(id: $ty) -> Self {
                 Self(id)
             }
 
             /// Returns the inner value of the id.
-            pub fn get(&self) -> u64 {
+            pub fn get(&self) -> $ty {
                 self.0
             }
         }
@@ -44,11 +64,25 @@ macro_rules! id {
             }
         }
 
-        impl From<u64> for $id {
-            fn from(id: u64) -> $id {
+        impl From<$ty> for $id {
+            fn from(id: $ty) -> $id {
                 $id(id)
             }
         }
+
+        impl std::convert::AsRef<$ty> for $id {
+            fn as_ref(&self) -> &$ty {
+                &self.0
+            }
+        }
+
+        impl std::ops::Deref for $id {
+            type Target = $ty;
+
+            fn deref(&self) -> &$ty {
+                &self.0
+            }
+        }
     };
 }
 
@@ -67,6 +101,26 @@ macro_rules! id {
 /// let username: Username = "jdno".into();
 /// println!("User {} registered", username);
 /// ```
+///
+/// # Validation
+///
+/// By default a name accepts any `&str` or `String` through its infallible `From` impls. Passing a
+/// `validate = ...` predicate instead turns the type into a parse-don't-validate boundary: the
+/// infallible conversions are replaced by fallible `TryFrom` and `FromStr` impls that run the
+/// predicate and return an [`InvalidName`] when it rejects the input. The generated `Deserialize`
+/// impl routes through the same check, so deserializing a bad value fails loudly rather than
+/// constructing an invalid name.
+///
+/// ```rust
+/// use std::convert::TryFrom;
+///
+/// use idtype::name;
+///
+/// name!(Username, validate = |s: &str| !s.is_empty() && s.len() <= 39);
+///
+/// assert!(Username::try_from("jdno").is_ok());
+/// assert!(Username::try_from("").is_err());
+/// ```
 #[macro_export]
 macro_rules! name {
     (
@@ -106,15 +160,171 @@ macro_rules! name {
                 $name(string)
             }
         }
+
+        impl std::convert::AsRef<str> for $name {
+            fn as_ref(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl std::borrow::Borrow<str> for $name {
+            fn borrow(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl std::ops::Deref for $name {
+            type Target = str;
+
+            fn deref(&self) -> &str {
+                &self.0
+            }
+        }
     };
+
+    (
+        $(#[$meta:meta])*
+        $name:ident, validate = $validate:expr
+    ) => {
+        $(#[$meta])*
+        #[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, $crate::serde::Serialize)]
+        pub struct $name(String);
+
+        impl $name {
+            /// Returns the inner value of the name.
+            pub fn get(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl std::fmt::Display for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                write!(f, "{}", self.0)
+            }
+        }
+
+        impl std::convert::TryFrom<&str> for $name {
+            type Error = $crate::InvalidName;
+
+            fn try_from(value: &str) -> std::result::Result<Self, Self::Error> {
+                let validate: fn(&str) -> bool = $validate;
+                if validate(value) {
+                    Ok($name(value.into()))
+                } else {
+                    Err($crate::InvalidName::new(stringify!($name), value))
+                }
+            }
+        }
+
+        impl std::convert::TryFrom<String> for $name {
+            type Error = $crate::InvalidName;
+
+            fn try_from(value: String) -> std::result::Result<Self, Self::Error> {
+                std::convert::TryFrom::try_from(value.as_str())
+            }
+        }
+
+        impl std::str::FromStr for $name {
+            type Err = $crate::InvalidName;
+
+            fn from_str(value: &str) -> std::result::Result<Self, Self::Err> {
+                std::convert::TryFrom::try_from(value)
+            }
+        }
+
+        impl<'de> $crate::serde::Deserialize<'de> for $name {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+            where
+                D: $crate::serde::Deserializer<'de>,
+            {
+                use $crate::serde::de::Error;
+                let value = <String as $crate::serde::Deserialize>::deserialize(deserializer)?;
+                std::convert::TryFrom::try_from(value).map_err(D::Error::custom)
+            }
+        }
+
+        impl std::convert::AsRef<str> for $name {
+            fn as_ref(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl std::borrow::Borrow<str> for $name {
+            fn borrow(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl std::ops::Deref for $name {
+            type Target = str;
+
+            fn deref(&self) -> &str {
+                &self.0
+            }
+        }
+    };
+}
+
+/// Error returned when a validated [`name!`] type rejects an input.
+///
+/// The error records the name of the type that rejected the value and the offending value itself,
+/// so callers can surface a helpful message.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InvalidName {
+    type_name: &'static str,
+    value: String,
 }
 
+impl InvalidName {
+    /// Initializes a new error for the given type and offending value.
+    pub fn new(type_name: &'static str, value: impl Into<String>) -> Self {
+        Self {
+            type_name,
+            value: value.into(),
+        }
+    }
+
+    /// Returns the name of the type that rejected the value.
+    pub fn type_name(&self) -> &str {
+        self.type_name
+    }
+
+    /// Returns the value that failed validation.
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+}
+
+impl std::fmt::Display for InvalidName {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "'{}' is not a valid {}", self.value, self.type_name)
+    }
+}
+
+impl std::error::Error for InvalidName {}
+
 /// Generate a secret type
 ///
 /// The `secret!` macro generates a type for secrets such as passwords or tokens. The type uses the
 /// [`secrecy`](https://crates.io/crates/secrecy) crate internally to prevent accidentally leaking
 /// the inner value in debug or log statements.
 ///
+/// By default the secret protects UTF-8 text and wraps a [`secrecy::SecretString`]. Credentials
+/// that are raw bytes (HMAC keys, DER blobs, binary tokens) can be protected with the same
+/// guarantees by passing the payload type explicitly. A `Vec<T>` payload wraps a
+/// [`secrecy::SecretVec`] and exposes a `&[T]`, while any other type wraps a [`secrecy::Secret`]
+/// and exposes a `&T`. The payload must implement [`secrecy::zeroize::Zeroize`]; the typed forms
+/// additionally require it to be [`Clone`] (the element type `T` for `Vec<T>` payloads) so the
+/// generated type can clone the protected value through its wrapper.
+///
+/// Two secrets of the same type compare for equality in constant time via the
+/// [`subtle`](https://crates.io/crates/subtle) crate, so the generated types are safe to use
+/// directly in authentication checks. The comparison short-circuits only on a length mismatch;
+/// the length of a secret is not itself treated as confidential, which holds for the token
+/// schemes these types are built for. For the typed forms this requires the payload to implement
+/// [`subtle::ConstantTimeEq`] (for `Vec<T>` payloads, the element type `T`); the default string
+/// form always satisfies this as it compares the exposed bytes.
+///
 /// # Example
 ///
 /// ```rust
@@ -125,8 +335,18 @@ macro_rules! name {
 /// let token: ApiToken = "super-secret-api-token".into();
 /// let header = format!("Authorization: Bearer {}", token.expose());
 /// ```
+///
+/// ```rust
+/// use idtype::secret;
+///
+/// secret!(SigningKey: Vec<u8>);
+///
+/// let key = SigningKey::new(vec![0x13, 0x37]);
+/// assert_eq!(&[0x13, 0x37], key.expose());
+/// ```
 #[macro_export]
 macro_rules! secret {
+    // Default form: a secret protecting UTF-8 text.
     (
         $(#[$meta:meta])*
         $secret:ident
@@ -154,6 +374,26 @@ macro_rules! secret {
             }
         }
 
+        #[cfg(feature = "serialize-secrets")]
+        impl $crate::serde::Serialize for $secret {
+            fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+            where
+                S: $crate::serde::Serializer,
+            {
+                $crate::serde::Serialize::serialize(&self.expose(), serializer)
+            }
+        }
+
+        #[cfg(not(feature = "serialize-secrets"))]
+        impl $crate::serde::Serialize for $secret {
+            fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+            where
+                S: $crate::serde::Serializer,
+            {
+                serializer.serialize_str("[REDACTED]")
+            }
+        }
+
         impl From<&str> for $secret {
             fn from(secret: &str) -> $secret {
                 $secret($crate::secrecy::SecretString::new(String::from(secret)))
@@ -165,6 +405,167 @@ macro_rules! secret {
                 $secret($crate::secrecy::SecretString::new(secret))
             }
         }
+
+        impl PartialEq for $secret {
+            fn eq(&self, other: &Self) -> bool {
+                use $crate::subtle::ConstantTimeEq;
+                self.expose().as_bytes().ct_eq(other.expose().as_bytes()).into()
+            }
+        }
+
+        impl Eq for $secret {}
+    };
+
+    // Byte-slice form: a secret protecting a `Vec<T>` of zeroizing payloads.
+    (
+        $(#[$meta:meta])*
+        $secret:ident: Vec<$payload:ty>
+    ) => {
+        $(#[$meta])*
+        #[derive($crate::serde::Deserialize)]
+        pub struct $secret($crate::secrecy::SecretVec<$payload>);
+
+        impl $secret {
+            /// Initializes a new secret.
+            pub fn new(secret: Vec<$payload>) -> Self {
+                Self($crate::secrecy::SecretVec::new(secret))
+            }
+
+            /// Returns the inner value of the secret.
+            pub fn expose(&self) -> &[$payload] {
+                use $crate::secrecy::ExposeSecret;
+                self.0.expose_secret()
+            }
+        }
+
+        impl Clone for $secret {
+            fn clone(&self) -> Self {
+                Self::new(self.expose().to_vec())
+            }
+        }
+
+        impl std::fmt::Debug for $secret {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                write!(f, "[REDACTED]")
+            }
+        }
+
+        impl std::fmt::Display for $secret {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                write!(f, "[REDACTED]")
+            }
+        }
+
+        #[cfg(feature = "serialize-secrets")]
+        impl $crate::serde::Serialize for $secret {
+            fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+            where
+                S: $crate::serde::Serializer,
+            {
+                $crate::serde::Serialize::serialize(&self.expose(), serializer)
+            }
+        }
+
+        #[cfg(not(feature = "serialize-secrets"))]
+        impl $crate::serde::Serialize for $secret {
+            fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+            where
+                S: $crate::serde::Serializer,
+            {
+                serializer.serialize_str("[REDACTED]")
+            }
+        }
+
+        impl From<Vec<$payload>> for $secret {
+            fn from(secret: Vec<$payload>) -> $secret {
+                $secret($crate::secrecy::SecretVec::new(secret))
+            }
+        }
+
+        impl PartialEq for $secret {
+            fn eq(&self, other: &Self) -> bool {
+                use $crate::subtle::ConstantTimeEq;
+                self.expose().ct_eq(other.expose()).into()
+            }
+        }
+
+        impl Eq for $secret {}
+    };
+
+    // Single-value form: a secret protecting an arbitrary zeroizing payload.
+    (
+        $(#[$meta:meta])*
+        $secret:ident: $payload:ty
+    ) => {
+        $(#[$meta])*
+        #[derive($crate::serde::Deserialize)]
+        pub struct $secret($crate::secrecy::Secret<$payload>);
+
+        impl $secret {
+            /// Initializes a new secret.
+            pub fn new(secret: $payload) -> Self {
+                Self($crate::secrecy::Secret::new(secret))
+            }
+
+            /// Returns the inner value of the secret.
+            pub fn expose(&self) -> &$payload {
+                use $crate::secrecy::ExposeSecret;
+                self.0.expose_secret()
+            }
+        }
+
+        impl Clone for $secret {
+            fn clone(&self) -> Self {
+                Self::new(self.expose().clone())
+            }
+        }
+
+        impl std::fmt::Debug for $secret {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                write!(f, "[REDACTED]")
+            }
+        }
+
+        impl std::fmt::Display for $secret {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                write!(f, "[REDACTED]")
+            }
+        }
+
+        #[cfg(feature = "serialize-secrets")]
+        impl $crate::serde::Serialize for $secret {
+            fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+            where
+                S: $crate::serde::Serializer,
+            {
+                $crate::serde::Serialize::serialize(&self.expose(), serializer)
+            }
+        }
+
+        #[cfg(not(feature = "serialize-secrets"))]
+        impl $crate::serde::Serialize for $secret {
+            fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+            where
+                S: $crate::serde::Serializer,
+            {
+                serializer.serialize_str("[REDACTED]")
+            }
+        }
+
+        impl From<$payload> for $secret {
+            fn from(secret: $payload) -> $secret {
+                $secret($crate::secrecy::Secret::new(secret))
+            }
+        }
+
+        impl PartialEq for $secret {
+            fn eq(&self, other: &Self) -> bool {
+                use $crate::subtle::ConstantTimeEq;
+                self.expose().ct_eq(other.expose()).into()
+            }
+        }
+
+        impl Eq for $secret {}
     };
 }
 
@@ -199,6 +600,20 @@ mod tests {
             let _id: TestId = 42.into();
         }
 
+        #[test]
+        fn trait_as_ref() {
+            let id = TestId::new(42);
+
+            assert_eq!(&42, id.as_ref());
+        }
+
+        #[test]
+        fn trait_deref() {
+            let id = TestId::new(42);
+
+            assert_eq!(42, *id);
+        }
+
         #[test]
         fn trait_send() {
             fn assert_send<T: Send>() {}
@@ -218,6 +633,50 @@ mod tests {
         }
     }
 
+    mod typed_id {
+        id!(
+            /// Identifier with a custom backing type for tests
+            SmallTestId: u32
+        );
+
+        #[test]
+        fn id() {
+            let id = SmallTestId::new(42);
+
+            assert_eq!(42u32, id.get());
+        }
+
+        #[test]
+        fn trait_display() {
+            let id = SmallTestId::new(42);
+
+            assert_eq!("42", id.to_string());
+        }
+
+        #[test]
+        fn trait_from_u32() {
+            let _id: SmallTestId = 42u32.into();
+        }
+
+        #[test]
+        fn trait_send() {
+            fn assert_send<T: Send>() {}
+            assert_send::<SmallTestId>();
+        }
+
+        #[test]
+        fn trait_sync() {
+            fn assert_sync<T: Sync>() {}
+            assert_sync::<SmallTestId>();
+        }
+
+        #[test]
+        fn trait_unpin() {
+            fn assert_unpin<T: Unpin>() {}
+            assert_unpin::<SmallTestId>();
+        }
+    }
+
     mod name {
         use super::*;
 
@@ -250,6 +709,31 @@ mod tests {
             let _name: TestName = String::from("test").into();
         }
 
+        #[test]
+        fn trait_as_ref() {
+            let name = TestName::new("test");
+
+            assert_eq!("test", name.as_ref() as &str);
+        }
+
+        #[test]
+        fn trait_deref() {
+            let name = TestName::new("test");
+
+            assert_eq!("test", &*name);
+            assert_eq!(4, name.len());
+        }
+
+        #[test]
+        fn trait_borrow() {
+            use std::collections::HashMap;
+
+            let mut map: HashMap<TestName, u8> = HashMap::new();
+            map.insert(TestName::new("test"), 42);
+
+            assert_eq!(Some(&42), map.get("test"));
+        }
+
         #[test]
         fn trait_send() {
             fn assert_send<T: Send>() {}
@@ -269,6 +753,64 @@ mod tests {
         }
     }
 
+    mod validated_name {
+        use std::convert::TryFrom;
+        use std::str::FromStr;
+
+        name!(
+            /// Validated name for tests
+            ValidatedName,
+            validate = |s: &str| !s.is_empty() && s.len() <= 5
+        );
+
+        #[test]
+        fn trait_try_from_str() {
+            let name = ValidatedName::try_from("test").unwrap();
+
+            assert_eq!("test", name.get());
+        }
+
+        #[test]
+        fn trait_try_from_str_invalid() {
+            assert!(ValidatedName::try_from("").is_err());
+            assert!(ValidatedName::try_from("too long").is_err());
+        }
+
+        #[test]
+        fn trait_try_from_string() {
+            let name = ValidatedName::try_from(String::from("test")).unwrap();
+
+            assert_eq!("test", name.get());
+        }
+
+        #[test]
+        fn trait_from_str() {
+            let name = ValidatedName::from_str("test").unwrap();
+
+            assert_eq!("test", name.get());
+
+            assert!(ValidatedName::from_str("invalid").is_err());
+        }
+
+        #[test]
+        fn trait_send() {
+            fn assert_send<T: Send>() {}
+            assert_send::<ValidatedName>();
+        }
+
+        #[test]
+        fn trait_sync() {
+            fn assert_sync<T: Sync>() {}
+            assert_sync::<ValidatedName>();
+        }
+
+        #[test]
+        fn trait_unpin() {
+            fn assert_unpin<T: Unpin>() {}
+            assert_unpin::<ValidatedName>();
+        }
+    }
+
     #[cfg(feature = "secret")]
     mod secret {
         use super::*;
@@ -302,6 +844,18 @@ mod tests {
             let _secret: TestSecret = "test".into();
         }
 
+        #[test]
+        fn trait_serialize() {
+            fn assert_serialize<T: serde::Serialize>() {}
+            assert_serialize::<TestSecret>();
+        }
+
+        #[test]
+        fn trait_eq() {
+            assert_eq!(TestSecret::new("test"), TestSecret::new("test"));
+            assert_ne!(TestSecret::new("test"), TestSecret::new("other"));
+        }
+
         #[test]
         fn trait_send() {
             fn assert_send<T: Send>() {}
@@ -320,4 +874,113 @@ mod tests {
             assert_unpin::<TestSecret>();
         }
     }
+
+    #[cfg(feature = "secret")]
+    mod secret_bytes {
+        secret!(
+            /// Binary secret for tests
+            TestSecretBytes: Vec<u8>
+        );
+
+        #[test]
+        fn secret() {
+            let secret = TestSecretBytes::new(vec![1, 2, 3]);
+
+            assert_eq!(&[1, 2, 3], secret.expose());
+        }
+
+        #[test]
+        fn trait_display() {
+            let secret = TestSecretBytes::new(vec![1, 2, 3]);
+
+            assert_eq!("[REDACTED]", secret.to_string());
+        }
+
+        #[test]
+        fn trait_from_vec() {
+            let _secret: TestSecretBytes = vec![1, 2, 3].into();
+        }
+
+        #[test]
+        fn trait_eq() {
+            assert_eq!(TestSecretBytes::new(vec![1, 2, 3]), TestSecretBytes::new(vec![1, 2, 3]));
+            assert_ne!(TestSecretBytes::new(vec![1, 2, 3]), TestSecretBytes::new(vec![4, 5, 6]));
+        }
+
+        #[test]
+        fn trait_send() {
+            fn assert_send<T: Send>() {}
+            assert_send::<TestSecretBytes>();
+        }
+
+        #[test]
+        fn trait_sync() {
+            fn assert_sync<T: Sync>() {}
+            assert_sync::<TestSecretBytes>();
+        }
+
+        #[test]
+        fn trait_unpin() {
+            fn assert_unpin<T: Unpin>() {}
+            assert_unpin::<TestSecretBytes>();
+        }
+    }
+
+    #[cfg(feature = "secret")]
+    mod secret_value {
+        secret!(
+            /// Single-value secret for tests
+            TestSecretValue: u8
+        );
+
+        #[test]
+        fn secret() {
+            let secret = TestSecretValue::new(42);
+
+            assert_eq!(&42, secret.expose());
+        }
+
+        #[test]
+        fn trait_clone() {
+            let secret = TestSecretValue::new(42);
+
+            assert_eq!(&42, secret.clone().expose());
+        }
+
+        #[test]
+        fn trait_display() {
+            let secret = TestSecretValue::new(42);
+
+            assert_eq!("[REDACTED]", secret.to_string());
+        }
+
+        #[test]
+        fn trait_from() {
+            let _secret: TestSecretValue = 42u8.into();
+        }
+
+        #[test]
+        fn trait_eq() {
+            assert_eq!(TestSecretValue::new(42), TestSecretValue::new(42));
+            assert_ne!(TestSecretValue::new(42), TestSecretValue::new(7));
+        }
+
+        #[test]
+        fn trait_send() {
+            fn assert_send<T: Send>() {}
+            assert_send::<TestSecretValue>();
+        }
+
+        #[test]
+        fn trait_sync() {
+            fn assert_sync<T: Sync>() {}
+            assert_sync::<TestSecretValue>();
+        }
+
+        #[test]
+        fn trait_unpin() {
+            fn assert_unpin<T: Unpin>() {}
+            assert_unpin::<TestSecretValue>();
+        }
+    }
 }